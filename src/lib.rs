@@ -1,192 +1,266 @@
 /*!
-This crate provides a library for generating a histogram of bigrams contained within a text file.
-The file has to be a valid UTF-8 format. Only ascii words are considered and trailing characters
-after a punctuation are dropped.
+This crate provides a library for generating a histogram of n-grams contained within a text file.
+The file is read as raw bytes, so it does not need to be valid UTF-8: only ascii words are
+considered and trailing characters after a punctuation are dropped, same as any stray non-UTF-8
+byte elsewhere in the file. The n-gram size (bigrams by default) is configurable through `Config`.
  */
-use regex::Regex;
+mod tokenizer;
+
+use rayon::prelude::*;
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
 use std::process;
-
+use tokenizer::WordTokenizer;
 
 /// Takes as an input a config object which contains path to a file. Runs through the file to
-/// generate a histogram of bigrams contained in the file and prints them out.
-/// Step 1: Initializes mutable objects counter_map, rolling_vector, key_tracker that will be used
-/// to keep track of the bigrams and their counts
-/// Step 2: Uses the read_lines function to get an iterable of lines of the file instead of loading
-/// all the contents in a String object
-/// Step 3: Iterates over the lines and passes each line to parse_text_into_vec function which
-/// returns a vector of the words contained in the line disregarding any spaces or punctuations
-/// Step 4: Iterates over the vector returned in the previous step and passes each element into the
-/// calculate_counts function along with the objects initialized in step 1 to update the bigram counts
-/// Step 5: Uses the key_tracker vector to iterate over the counter_map in order and writes the
-/// histogram to the output console
+/// generate a histogram of n-grams contained in the file and prints them out.
+/// Step 1: Uses the read_lines function to read the file as line-separated byte segments and
+/// collects them into a Vec<Vec<u8>>, so the whole file is held in memory (as raw bytes)
+/// Step 2: Counts the n-grams, either sequentially or across `config.num_threads` rayon workers
+/// depending on `config.parallel`, into a HashMap<String,u32>
+/// Step 3: Sorts the counted n-grams by descending count, breaking ties lexicographically on the
+/// key, so the printed histogram is deterministic and reproducible across runs
+/// Step 4: Prints the sorted histogram, truncated to `config.top_k` entries when set
+///
+/// When `config.parallel` is set, the file's lines are split into `config.num_threads` roughly
+/// equal contiguous chunks that are counted independently with rayon and then reduced into a
+/// single map, see `run_parallel` for the chunk-boundary stitching this requires.
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
 
-    let mut counter_map: HashMap<String, u32> = HashMap::new();
-
-    let mut rolling_vector: Vec<String> = vec![];
-
-    let re = get_regex();
-
     let lines = read_lines(&config.filename).unwrap_or_else(|err| {
         println!("Cannot read the file: {}", err);
         process::exit(9);
     });
 
-    for (line_no, line) in lines.enumerate() {
+    let line_vec: Vec<Vec<u8>> = lines
+        .enumerate()
+        .map(|(line_no, line)| {
+            line.unwrap_or_else(|err| {
+                println!("Could not read line no {}: {}", line_no, err);
+                process::exit(9);
+            })
+        })
+        .collect();
 
-        let line = line.unwrap_or_else(|err| {
-            println!("Could not read line no {}: {}", line_no, err);
-            process::exit(9);
-        });
+    let counter_map = if config.parallel {
+        run_parallel(&line_vec, config.num_threads, config.n)
+    } else {
+        run_sequential(&line_vec, config.n)
+    };
 
-        let line_word_vec = parse_text_into_vec(&line,&re);
+    let total = counter_map.len();
+    let sorted_counts = sort_counts_by_frequency(counter_map);
 
-        for text in line_word_vec.iter() {
-            calculate_counts(
-                &mut counter_map,
-                &mut rolling_vector,
-                text
-            );
-        }
-    }
+    let displayed = match config.top_k {
+        Some(top_k) => &sorted_counts[..sorted_counts.len().min(top_k)],
+        None => &sorted_counts[..],
+    };
 
-    for (k,v) in counter_map.iter() {
+    for (k, v) in displayed.iter() {
         println!("•\t\"{}\" {}", k, v);
     }
 
-    println!(
-        "Total no. of bigrams generated: {}",
-        counter_map.keys().len()
-    );
+    println!("Total no. of {}-grams generated: {}", config.n, total);
 
     Ok(())
 }
 
-fn get_regex() -> Regex {
-    Regex::new(r"[^a-z0-9 ]+").unwrap()
+/// Collects the counted n-grams into a `Vec` sorted by descending count, with ties broken
+/// lexicographically on the key. Sorting by `Reverse(count)` then key gives a stable,
+/// reproducible order instead of the indeterminate order a `HashMap` iterates in.
+fn sort_counts_by_frequency(counter_map: HashMap<String, u32>) -> Vec<(String, u32)> {
+    let mut sorted_counts: Vec<(String, u32)> = counter_map.into_iter().collect();
+    sorted_counts.sort_by_key(|(key, count)| (Reverse(*count), key.clone()));
+    sorted_counts
 }
 
-/// Takes as an input a string slice and splits it into a vector by splitting on white space
-/// as well as manipulating the rendered elements through the cleanse_word function.
-fn parse_text_into_vec(line: &str, re:&Regex) -> Vec<String> {
-    line.split_whitespace()
-        .filter(|s| cleanse_word(s.to_ascii_lowercase().as_str(), re).is_some())
-        .map(|s| {
-            cleanse_word(s.to_ascii_lowercase().as_str(), re)
-                .unwrap()
-                .to_string()
-        })
-        .collect()
-}
+/// Counts n-grams across all of `lines` on a single thread, the same way `run` always used to.
+fn run_sequential(lines: &[Vec<u8>], n: usize) -> HashMap<String, u32> {
+    let mut counter_map: HashMap<String, u32> = HashMap::new();
+    let mut rolling_vector: Vec<String> = vec![];
 
-/// Takes as input a string slice and a compiled regex pattern and returns an optional containing
-/// either a string slice.
-/// 1. If the regex matches any character of the string at an index > 0 then it strips away the
-/// string from there and returns the leftover wrapped in a Some()
-/// 2. If the regex matches at the start of the string then it strips away at the start until it
-/// finds a non matching character. If there are no matches after that then it returns the slice.
-/// 3. If the regex matches at the start of the string then it strips away at the start until it
-/// finds a non matching character. If there is again a match then it splits the string from that
-/// point and returns the slice.
-/// 4. If everything matches the regex then it returns a None
-/// 5. If nothing matches the regex it returns the original slice in a Some()
-fn cleanse_word<'a>(text: &'a str, re: &Regex) -> Option<&'a str> {
-    if re.is_match(text) {
-        let start_idx = re.find(text).unwrap().start();
-        let end_idx = re.find(text).unwrap().end();
-
-        if start_idx != 0 {
-            //The punctuations come after the word, split and disregard the rest
-            return Some(text.split_at(start_idx).0);
+    for line in lines {
+        for text in WordTokenizer::new(line) {
+            calculate_counts(&mut counter_map, &mut rolling_vector, &text, n);
         }
+    }
 
-        if start_idx == 0 && text.len() > end_idx {
-            //Check for the case where there are punctuations at the start
-            //It can have two sub-cases:
-            //1. The punctuations surround the word on both sides
-            //2. The punctuations are only at the beginning
-            let temp = text.split_at(end_idx).1;
-
-            if re.is_match(temp) {
-                //if there is a match that means that the word is surrounded by punctuations
-                //like ......harry...''''' and we have split the first part off so now we are
-                //left with harry...'''''
-                //We need to discard the trailing punctuations as done in the first case
-                return Some(temp.split_at(re.find(temp).unwrap().start()).0);
-            } else {
-                //The punctuations are only at the beginning so we can return the split word
-                return Some(temp);
+    counter_map
+}
+
+/// Counts n-grams across all of `lines` by splitting them into `num_threads` roughly equal
+/// contiguous chunks and counting each chunk in parallel with rayon, then reducing the per-chunk
+/// maps into one.
+///
+/// N-grams span line boundaries because `rolling_vector` persists across lines in
+/// `run_sequential`, so they also span chunk boundaries here: each chunk additionally reports its
+/// leading and trailing `n - 1` words, and its total word count, so the reduce step can re-form
+/// every boundary-crossing n-gram that would otherwise be lost by counting chunks in isolation —
+/// including n-grams that span a short or empty chunk entirely, see `merge_chunk_results`.
+fn run_parallel(lines: &[Vec<u8>], num_threads: usize, n: usize) -> HashMap<String, u32> {
+    let num_chunks = num_threads.max(1).min(lines.len().max(1));
+    let chunk_size = (lines.len() + num_chunks - 1) / num_chunks.max(1);
+    let chunk_size = chunk_size.max(1);
+
+    let chunk_results: Vec<ChunkResult> = lines
+        .par_chunks(chunk_size)
+        .map(|chunk| count_chunk(chunk, n))
+        .collect();
+
+    merge_chunk_results(chunk_results, n)
+}
+
+/// The counts produced for a single contiguous chunk of lines, along with the chunk's leading and
+/// trailing `n - 1` words and its total word count so that cross-chunk n-grams can be stitched
+/// back in during the reduce step.
+struct ChunkResult {
+    counts: HashMap<String, u32>,
+    leading_words: Vec<String>,
+    trailing_words: Vec<String>,
+    word_count: usize,
+}
+
+/// Counts n-grams within a single chunk of lines exactly as `run_sequential` would, and records
+/// the chunk's leading and trailing `n - 1` words, plus its total word count, for boundary
+/// stitching.
+fn count_chunk(chunk: &[Vec<u8>], n: usize) -> ChunkResult {
+    let boundary_len = n.saturating_sub(1);
+
+    let mut counter_map: HashMap<String, u32> = HashMap::new();
+    let mut rolling_vector: Vec<String> = vec![];
+    let mut leading_words: Vec<String> = vec![];
+    let mut trailing_words: Vec<String> = vec![];
+    let mut word_count: usize = 0;
+
+    for line in chunk {
+        for text in WordTokenizer::new(line) {
+            word_count += 1;
+
+            if leading_words.len() < boundary_len {
+                leading_words.push(text.clone());
+            }
+
+            trailing_words.push(text.clone());
+            if trailing_words.len() > boundary_len {
+                trailing_words.remove(0);
             }
+
+            calculate_counts(&mut counter_map, &mut rolling_vector, &text, n);
         }
+    }
+
+    ChunkResult {
+        counts: counter_map,
+        leading_words,
+        trailing_words,
+        word_count,
+    }
+}
+
+/// Reduces the per-chunk maps produced by `count_chunk` into a single map, entry-wise summing
+/// counts, then walks the chunks in order threading a `carry` buffer of up to `n - 1` words
+/// forward from one chunk to the next. At each chunk, `carry` is combined with that chunk's
+/// leading words and every `n`-length window starting inside `carry` is a boundary-crossing
+/// n-gram that `count_chunk` could not have seen; `carry` is then replaced by the chunk's own
+/// trailing words (or, if the chunk has fewer than `n - 1` words in total, by `carry` with the
+/// chunk's words appended). Carrying forward this way — rather than only stitching pairs of
+/// adjacent chunks — means an n-gram is still found even when it spans a short or entirely empty
+/// chunk (e.g. a blank line) sitting between its first and last word.
+fn merge_chunk_results(chunk_results: Vec<ChunkResult>, n: usize) -> HashMap<String, u32> {
+    let mut counter_map: HashMap<String, u32> = HashMap::new();
+
+    for chunk_result in chunk_results.iter() {
+        for (k, v) in chunk_result.counts.iter() {
+            *counter_map.entry(k.clone()).or_insert(0) += v;
+        }
+    }
 
-        if start_idx == 0 && text.len() == end_idx {
-            //Its all punctuations no need to do anything
-            return None;
+    if n > 1 {
+        let boundary_len = n - 1;
+        let mut carry: Vec<String> = vec![];
+
+        for chunk_result in chunk_results.iter() {
+            let combined: Vec<String> = carry
+                .iter()
+                .cloned()
+                .chain(chunk_result.leading_words.iter().cloned())
+                .collect();
+
+            if combined.len() >= n {
+                let max_start = (combined.len() - n + 1).min(carry.len());
+                for start in 0..max_start {
+                    let key = get_key_from_vec(&combined[start..start + n]);
+                    *counter_map.entry(key).or_insert(0) += 1;
+                }
+            }
+
+            if chunk_result.word_count >= boundary_len {
+                carry = chunk_result.trailing_words.clone();
+            } else {
+                carry.extend(chunk_result.trailing_words.iter().cloned());
+                if carry.len() > boundary_len {
+                    let drop = carry.len() - boundary_len;
+                    carry.drain(0..drop);
+                }
+            }
         }
     }
-    //The word is clean already, return as is
-    Some(text)
+
+    counter_map
 }
 
-/// Takes as an input a mutable reference to a HashMap<String,u32>, a mutable reference to
-/// two Vec<String> and a String slice. One vector (rolling_vector) is used to keep track of
-/// bigram keys and it rolls over with each new bigram. The other vector (key_tracker) keeps track
-/// of the sequence of bigrams as they were found in the text file. Since by default the iteration
-/// of a Map is indeterminate, therefore we will lose track of the sequence of bigrams without
-/// this vector. The string slice is the next word in the stream of words from the file, the
-/// function decides whether to increase the count of an already existing bigram or to add this
-/// word to another previous word to create a new bigram
-#[allow(mutable_borrow_reservation_conflict)]
+/// Takes as an input a mutable reference to a HashMap<String,u32>, a mutable reference to a
+/// Vec<String> and a String slice. The vector (rolling_vector) keeps the last `n` words seen and
+/// rolls over with each new word. The string slice is the next word in the stream of words from
+/// the file; the function pushes it onto the rolling window and, once the window holds `n` words,
+/// records the n-gram it forms and slides the window forward by one word (dropping the front,
+/// keeping the remaining `n - 1` words) so the next call starts building the following n-gram.
 fn calculate_counts(
     counter_map: &mut HashMap<String, u32>,
     rolling_vector: &mut Vec<String>,
-    word: &str
+    word: &str,
+    n: usize,
 ) {
-    if rolling_vector.len() < 2 {
+    if rolling_vector.len() < n {
         rolling_vector.push(word.to_string());
     }
-    if rolling_vector.len() == 2 {
-        let key = get_key_from_vec(&rolling_vector);
-        if counter_map.contains_key(&key) {
-            let count = counter_map.get(&key).unwrap();
-            counter_map.insert(key, count + 1);
-        } else {
-            counter_map.insert(key, 1);
-        }
-        //re-initialize the vector now with the second word
-        *rolling_vector = vec![rolling_vector.get(1).unwrap().to_string()];
+    if rolling_vector.len() == n {
+        let key = get_key_from_vec(rolling_vector);
+        *counter_map.entry(key).or_insert(0) += 1;
+
+        //slide the window forward by one word
+        rolling_vector.remove(0);
     }
 }
 
-/// Takes a vector and generates a string object from its two elements.
-/// This function assumes that the vector does indeed have two elements in it.
-fn get_key_from_vec(rolling_vector: &Vec<String>) -> String {
-    let mut key = String::new();
-    key.push_str(rolling_vector.get(0).unwrap());
-    key.push_str(" ");
-    key.push_str(rolling_vector.get(1).unwrap());
-    key
+/// Takes a slice and generates a space-joined string object from all of its elements.
+fn get_key_from_vec(rolling_vector: &[String]) -> String {
+    rolling_vector.join(" ")
 }
 
-/// Gives a Result object containing an iterable over lines of a file.
+/// Gives a Result object containing an iterable over the line-separated byte segments of a file.
 /// Much better to use this approach when dealing with a large file than putting all the
-/// contents in a String object
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+/// contents in a String object, and reading raw bytes instead of `String` lines means the file
+/// no longer has to be valid UTF-8.
+fn read_lines<P>(filename: P) -> io::Result<io::Split<io::BufReader<File>>>
 where
     P: AsRef<Path>,
 {
     let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    Ok(io::BufReader::new(file).split(b'\n'))
 }
 
 #[derive(Debug)]
 pub struct Config {
     filename: String,
+    parallel: bool,
+    num_threads: usize,
+    n: usize,
+    top_k: Option<usize>,
 }
 
 impl Config {
@@ -194,8 +268,45 @@ impl Config {
         if args.len() < 2 {
             return Err("Not enough args");
         }
+
+        let mut parallel = false;
+        let mut num_threads: usize = 4;
+        let mut n: usize = 2;
+        let mut top_k: Option<usize> = None;
+
+        let mut idx = 2;
+        while idx < args.len() {
+            match args[idx].as_str() {
+                "--parallel" => parallel = true,
+                "--threads" => {
+                    idx += 1;
+                    let value = args.get(idx).ok_or("--threads needs a value")?;
+                    num_threads = value.parse().map_err(|_| "--threads needs a number")?;
+                }
+                "--n" => {
+                    idx += 1;
+                    let value = args.get(idx).ok_or("--n needs a value")?;
+                    n = value.parse().map_err(|_| "--n needs a number")?;
+                    if n == 0 {
+                        return Err("--n must be at least 1");
+                    }
+                }
+                "--top-k" => {
+                    idx += 1;
+                    let value = args.get(idx).ok_or("--top-k needs a value")?;
+                    top_k = Some(value.parse().map_err(|_| "--top-k needs a number")?);
+                }
+                _ => {}
+            }
+            idx += 1;
+        }
+
         Ok(Config {
             filename: args[1].clone(),
+            parallel,
+            num_threads,
+            n,
+            top_k,
         })
     }
     pub fn get_file_name(&self) -> &str {
@@ -208,198 +319,193 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_text_into_vec_with_no_punctuations_and_all_uppercase() {
-        let line = "THE QUICK BROWN FOX AND THE QUICK BROWN HARE";
-        let v = parse_text_into_vec(line,&get_regex());
-        assert!(v.contains(&"quick".to_string()));
-        assert!(!v.contains(&"THE".to_string()));
-        assert_eq!(v.len(), 9);
+    fn test_get_key_from_vec() {
+        let vec: Vec<String> = vec!["key1".to_string(), "key2".to_string()];
+        assert_eq!(get_key_from_vec(&vec), String::from("key1 key2"));
     }
 
     #[test]
-    fn test_parse_text_into_vec_with_no_punctuations_and_mixedcase() {
-        let line = "THE quICK brOWn FOX AND ThE QuiCK BROWN haRE";
-        let v = parse_text_into_vec(line,&get_regex());
-        assert!(v.contains(&"quick".to_string()));
-        assert!(!v.contains(&"THE".to_string()));
-        assert_eq!(v.len(), 9);
+    fn test_get_key_from_vec_with_empty_vector() {
+        let vec: Vec<String> = vec![];
+        assert_eq!(get_key_from_vec(&vec), String::from(""));
     }
 
     #[test]
-    fn test_parse_text_into_vec_with_no_punctuations_and_mixedcase_and_extra_spaces() {
-        let line = "THE quICK brOWn             FOX AND      ThE             QuiCK BROWN haRE";
-        let v = parse_text_into_vec(line,&get_regex());
-        assert!(v.contains(&"quick".to_string()));
-        assert!(!v.contains(&"THE".to_string()));
-        assert_eq!(v.get(8), Some(&"hare".to_string()));
-        assert_eq!(v.len(), 9);
+    fn test_get_key_from_vec_with_three_elements() {
+        let vec: Vec<String> = vec!["key1".to_string(), "key2".to_string(), "key3".to_string()];
+        assert_eq!(get_key_from_vec(&vec), String::from("key1 key2 key3"));
     }
 
     #[test]
-    fn test_parse_text_into_vec_with_punctuations_at_end_and_mixedcase_and_extra_spaces() {
-        let line = "THE quICK's brOWn'ss             FOX...??? AND      ThE             QuiCK BROWN haRE'ssssss";
-        let v = parse_text_into_vec(line,&get_regex());
-        assert!(v.contains(&"quick".to_string()));
-        assert!(!v.contains(&"THE".to_string()));
-        assert_eq!(v.get(8), Some(&"hare".to_string()));
-        assert_eq!(v.len(), 9);
-    }
+    fn test_calculate_counts() {
+        let mut counter_map: HashMap<String, u32> = HashMap::new();
+        let mut rolling_vector: Vec<String> = Vec::new();
 
-    #[test]
-    fn test_parse_text_into_vec_with_punctuations_at_start_and_mixedcase_and_extra_spaces() {
-        let line =
-            "THE .......quICK brOWn         FOX AND      ThE             QuiCK BROWN \"\"\"haRE";
-        let v = parse_text_into_vec(line,&get_regex());
-        assert!(v.contains(&"quick".to_string()));
-        assert!(!v.contains(&"THE".to_string()));
-        assert_eq!(v.get(8), Some(&"hare".to_string()));
-        assert_eq!(v.get(1), Some(&"quick".to_string()));
-        assert_eq!(v.len(), 9);
-    }
+        let words = [
+            "the", "quick", "brown", "fox", "and", "the", "quick", "blue", "hare",
+        ];
 
-    #[test]
-    fn test_parse_text_into_vec_with_enclosing_punctuations_and_mixedcase_and_extra_spaces() {
-        let line =
-            "THE .......quICK!!!!! .....brOWn'ssss         FOX AND      ThE             QuiCK BROWN \"\"\"haRE\"\".....??????";
-        let v = parse_text_into_vec(line,&get_regex());
-        assert!(v.contains(&"quick".to_string()));
-        assert!(!v.contains(&"THE".to_string()));
-        assert_eq!(v.get(8), Some(&"hare".to_string()));
-        assert_eq!(v.get(1), Some(&"quick".to_string()));
-        assert_eq!(v.get(2), Some(&"brown".to_string()));
-        assert_eq!(v.len(), 9);
-    }
+        for word in words.iter() {
+            calculate_counts(&mut counter_map, &mut rolling_vector, word, 2);
+        }
 
-    #[test]
-    fn test_parse_text_into_vec_with_enclosing_punctuations_and_mixedcase_and_extra_spaces_and_non_ascii(
-    ) {
-        let line =
-            "THE ૱﷼₢quICK₱€₴ brOWn🤯🤯🤯         FOX AND      ThE             QuiCK BROWN \"\"\"🥰🥰🥰haRE\"\"..😍😍😍...??????";
-        let v = parse_text_into_vec(line,&get_regex());
-        assert!(v.contains(&"quick".to_string()));
-        assert!(!v.contains(&"THE".to_string()));
-        assert_eq!(v.get(8), Some(&"hare".to_string()));
-        assert_eq!(v.get(1), Some(&"quick".to_string()));
-        assert_eq!(v.get(2), Some(&"brown".to_string()));
-        assert_eq!(v.len(), 9);
+        assert_eq!(*counter_map.get("the quick").unwrap(), 2 as u32);
+        assert_eq!(*counter_map.get("quick blue").unwrap(), 1 as u32);
+        assert_eq!(counter_map.contains_key("hare the"), false);
     }
 
     #[test]
-    fn test_parse_text_into_vec_with_all_punctuations_and_mixedcase_and_extra_spaces_and_non_ascii()
-    {
-        let line =
-            "THE ૱﷼₢quICK₱€₴ brOWn🤯🤯🤯         FOX AND    ???...;;;;   ThE    ₱€₴૱﷼₢;;../////         QuiCK BROWN \"\"\"🥰🥰🥰haRE\"\"..😍😍😍...??????";
-        let v = parse_text_into_vec(line,&get_regex());
-        assert!(v.contains(&"quick".to_string()));
-        assert!(!v.contains(&"THE".to_string()));
-        assert_eq!(v.get(8), Some(&"hare".to_string()));
-        assert_eq!(v.get(1), Some(&"quick".to_string()));
-        assert_eq!(v.get(2), Some(&"brown".to_string()));
-        assert_eq!(v.len(), 9);
-    }
+    fn test_calculate_counts_with_unigrams() {
+        let mut counter_map: HashMap<String, u32> = HashMap::new();
+        let mut rolling_vector: Vec<String> = Vec::new();
 
-    #[test]
-    fn test_cleanse_word_with_no_punctuations() {
-        let sample_text = "fox";
-        assert_eq!(cleanse_word(&sample_text, &get_regex()), Some("fox"));
+        for word in ["the", "quick", "the", "fox"].iter() {
+            calculate_counts(&mut counter_map, &mut rolling_vector, word, 1);
+        }
+
+        assert_eq!(*counter_map.get("the").unwrap(), 2 as u32);
+        assert_eq!(*counter_map.get("quick").unwrap(), 1 as u32);
+        assert_eq!(*counter_map.get("fox").unwrap(), 1 as u32);
     }
 
     #[test]
-    fn test_cleanse_word_with_punctuations_at_end() {
-        let sample_text = "fox's";
-        assert_eq!(cleanse_word(&sample_text, &get_regex()), Some("fox"));
+    fn test_calculate_counts_with_trigrams() {
+        let mut counter_map: HashMap<String, u32> = HashMap::new();
+        let mut rolling_vector: Vec<String> = Vec::new();
+
+        for word in ["the", "quick", "brown", "fox", "the", "quick", "brown"].iter() {
+            calculate_counts(&mut counter_map, &mut rolling_vector, word, 3);
+        }
+
+        assert_eq!(*counter_map.get("the quick brown").unwrap(), 2 as u32);
+        assert_eq!(*counter_map.get("quick brown fox").unwrap(), 1 as u32);
     }
 
     #[test]
-    fn test_cleanse_word_with_punctuations_at_start() {
-        let sample_text = "...???...,,,,```fox";
-        assert_eq!(cleanse_word(&sample_text, &get_regex()), Some("fox"));
+    fn test_run_parallel_matches_run_sequential() {
+        let lines: Vec<Vec<u8>> = vec![
+            "the quick brown fox",
+            "and the quick brown hare",
+            "jumps over the lazy dog",
+            "the dog barks at the fox",
+        ]
+        .into_iter()
+        .map(|s| s.as_bytes().to_vec())
+        .collect();
+
+        let sequential = run_sequential(&lines, 2);
+        let parallel = run_parallel(&lines, 3, 2);
+
+        assert_eq!(sequential, parallel);
     }
 
     #[test]
-    fn test_cleanse_word_with_punctuations_both_ends() {
-        let sample_text = "...???...,,,,```fox...!!!!!";
-        assert_eq!(cleanse_word(&sample_text, &get_regex()), Some("fox"));
+    fn test_run_parallel_matches_run_sequential_for_trigrams() {
+        let lines: Vec<Vec<u8>> = vec![
+            "the quick brown fox",
+            "and the quick brown hare",
+            "jumps over the lazy dog",
+            "the dog barks at the fox",
+        ]
+        .into_iter()
+        .map(|s| s.as_bytes().to_vec())
+        .collect();
+
+        let sequential = run_sequential(&lines, 3);
+        let parallel = run_parallel(&lines, 3, 3);
+
+        assert_eq!(sequential, parallel);
     }
 
     #[test]
-    fn test_cleanse_word_with_all_punctuations() {
-        let sample_text = "...???...,,,,```...!!!!!";
-        assert_eq!(cleanse_word(&sample_text, &get_regex()), None);
+    fn test_merge_chunk_results_stitches_chunk_boundary() {
+        let mut first_chunk_counts: HashMap<String, u32> = HashMap::new();
+        first_chunk_counts.insert("the quick".to_string(), 1);
+
+        let mut second_chunk_counts: HashMap<String, u32> = HashMap::new();
+        second_chunk_counts.insert("brown fox".to_string(), 1);
+
+        let chunk_results = vec![
+            ChunkResult {
+                counts: first_chunk_counts,
+                leading_words: vec!["the".to_string()],
+                trailing_words: vec!["quick".to_string()],
+                word_count: 2,
+            },
+            ChunkResult {
+                counts: second_chunk_counts,
+                leading_words: vec!["brown".to_string()],
+                trailing_words: vec!["fox".to_string()],
+                word_count: 2,
+            },
+        ];
+
+        let merged = merge_chunk_results(chunk_results, 2);
+
+        assert_eq!(*merged.get("the quick").unwrap(), 1);
+        assert_eq!(*merged.get("brown fox").unwrap(), 1);
+        assert_eq!(*merged.get("quick brown").unwrap(), 1);
+        assert_eq!(merged.keys().len(), 3);
     }
 
     #[test]
-    fn test_cleanse_word_with_emojis() {
-        let sample_text = "...???...,,,,```🥰😍fox...!!!!!";
-        assert_eq!(cleanse_word(&sample_text, &get_regex()), Some("fox"));
+    fn test_run_parallel_matches_run_sequential_across_blank_lines() {
+        let lines: Vec<Vec<u8>> = vec!["a b", "", "", "c d"]
+            .into_iter()
+            .map(|s| s.as_bytes().to_vec())
+            .collect();
+
+        let sequential = run_sequential(&lines, 2);
+        let parallel = run_parallel(&lines, 4, 2);
+
+        assert_eq!(sequential, parallel);
+        assert_eq!(*parallel.get("b c").unwrap(), 1);
     }
 
     #[test]
-    fn test_get_key_from_vec() {
-        let vec: Vec<String> = vec!["key1".to_string(), "key2".to_string()];
-        assert_eq!(get_key_from_vec(&vec), String::from("key1 key2"));
+    fn test_run_parallel_matches_run_sequential_with_short_chunks_for_trigrams() {
+        let lines: Vec<Vec<u8>> = vec!["a b", "c", "d e"]
+            .into_iter()
+            .map(|s| s.as_bytes().to_vec())
+            .collect();
+
+        let sequential = run_sequential(&lines, 3);
+        let parallel = run_parallel(&lines, 3, 3);
+
+        assert_eq!(sequential, parallel);
+        assert_eq!(*parallel.get("b c d").unwrap(), 1);
     }
 
     #[test]
-    #[should_panic]
-    fn test_get_key_from_vec_with_bad_vector() {
-        let vec: Vec<String> = vec![];
-        get_key_from_vec(&vec);
+    fn test_sort_counts_by_frequency_orders_by_descending_count() {
+        let mut counter_map: HashMap<String, u32> = HashMap::new();
+        counter_map.insert("the quick".to_string(), 3);
+        counter_map.insert("quick brown".to_string(), 5);
+        counter_map.insert("brown fox".to_string(), 1);
+
+        let sorted = sort_counts_by_frequency(counter_map);
+
+        assert_eq!(
+            sorted,
+            vec![
+                ("quick brown".to_string(), 5),
+                ("the quick".to_string(), 3),
+                ("brown fox".to_string(), 1),
+            ]
+        );
     }
 
     #[test]
-    fn test_calculate_counts() {
+    fn test_sort_counts_by_frequency_breaks_ties_lexicographically() {
         let mut counter_map: HashMap<String, u32> = HashMap::new();
-        let mut rolling_vector: Vec<String> = Vec::new();
+        counter_map.insert("zulu alpha".to_string(), 2);
+        counter_map.insert("alpha zulu".to_string(), 2);
 
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "the"
-        );
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "quick"
-        );
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "brown"
-        );
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "fox"
-        );
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "and"
-        );
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "the"
-        );
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "quick"
-        );
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "blue"
-        );
-        calculate_counts(
-            &mut counter_map,
-            &mut rolling_vector,
-            "hare"
-        );
+        let sorted = sort_counts_by_frequency(counter_map);
 
-        assert_eq!(*counter_map.get("the quick").unwrap(), 2 as u32);
-        assert_eq!(*counter_map.get("quick blue").unwrap(), 1 as u32);
-        assert_eq!(counter_map.contains_key("hare the"), false);
+        assert_eq!(
+            sorted,
+            vec![("alpha zulu".to_string(), 2), ("zulu alpha".to_string(), 2)]
+        );
     }
 }
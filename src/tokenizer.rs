@@ -0,0 +1,149 @@
+//! A small hand-written lexer that replaces regex-based word cleansing. It scans a line's bytes
+//! directly, splitting on ascii whitespace into tokens, and from each token takes only the first
+//! run of ASCII alphanumerics (lowercasing it), discarding any leading punctuation before that run
+//! and everything in the token after it. This preserves the old regex-based `cleanse_word`
+//! semantics exactly: a token like `fox's` yields only `fox`, not `fox` and `s` as two words.
+
+/// Iterates over the cleansed words contained in a slice of bytes: the bytes are split on ascii
+/// whitespace into tokens, and each token contributes at most one lowercased word, taken as its
+/// first run of ASCII alphanumerics. Leading punctuation is skipped, and anything in the token
+/// after that run — including further alphanumeric runs — is discarded, so `harry's` yields
+/// `harry` and a fully-punctuation token yields nothing.
+pub struct WordTokenizer<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> WordTokenizer<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        WordTokenizer { bytes, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for WordTokenizer<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            if self.pos >= self.bytes.len() {
+                return None;
+            }
+
+            while self.pos < self.bytes.len()
+                && !self.bytes[self.pos].is_ascii_whitespace()
+                && !self.bytes[self.pos].is_ascii_alphanumeric()
+            {
+                self.pos += 1;
+            }
+
+            let word_start = self.pos;
+            while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_alphanumeric() {
+                self.pos += 1;
+            }
+            let word_end = self.pos;
+
+            // Discard whatever is left of this whitespace-delimited token (e.g. a trailing
+            // "'s"), so the next iteration starts cleanly on the following token.
+            while self.pos < self.bytes.len() && !self.bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+
+            if word_end > word_start {
+                let word: Vec<u8> = self.bytes[word_start..word_end]
+                    .iter()
+                    .map(u8::to_ascii_lowercase)
+                    .collect();
+                return Some(String::from_utf8(word).expect("tokenized word should be ascii"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenize(text: &str) -> Vec<String> {
+        WordTokenizer::new(text.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_tokenizer_with_no_punctuations_and_mixedcase() {
+        let words = tokenize("THE quICK brOWn FOX AND ThE QuiCK BROWN haRE");
+        assert!(words.contains(&"quick".to_string()));
+        assert!(!words.contains(&"THE".to_string()));
+        assert_eq!(words.len(), 9);
+    }
+
+    #[test]
+    fn test_tokenizer_with_extra_whitespace() {
+        let words = tokenize("THE quICK brOWn             FOX AND      ThE             QuiCK BROWN haRE");
+        assert_eq!(words.get(8), Some(&"hare".to_string()));
+        assert_eq!(words.len(), 9);
+    }
+
+    #[test]
+    fn test_tokenizer_with_punctuation_at_end() {
+        let words = tokenize("fox's");
+        assert_eq!(words, vec!["fox".to_string()]);
+    }
+
+    #[test]
+    fn test_tokenizer_with_punctuation_at_start() {
+        let words = tokenize("...???...,,,,```fox");
+        assert_eq!(words, vec!["fox".to_string()]);
+    }
+
+    #[test]
+    fn test_tokenizer_with_punctuation_on_both_ends() {
+        let words = tokenize("...???...,,,,```fox...!!!!!");
+        assert_eq!(words, vec!["fox".to_string()]);
+    }
+
+    #[test]
+    fn test_tokenizer_with_all_punctuation_yields_nothing() {
+        let words = tokenize("...???...,,,,```...!!!!!");
+        assert!(words.is_empty());
+    }
+
+    #[test]
+    fn test_tokenizer_with_emojis() {
+        let words = tokenize("...???...,,,,```🥰😍fox...!!!!!");
+        assert_eq!(words, vec!["fox".to_string()]);
+    }
+
+    #[test]
+    fn test_tokenizer_with_apostrophe_drops_suffix() {
+        let words = tokenize("fox's den don't");
+        assert_eq!(words, vec!["fox".to_string(), "den".to_string(), "don".to_string()]);
+    }
+
+    #[test]
+    fn test_tokenizer_with_invalid_utf8_byte_inside_a_token() {
+        let mut bytes = b"quick".to_vec();
+        bytes.push(0xff);
+        bytes.extend_from_slice(b"brown");
+
+        let words: Vec<String> = WordTokenizer::new(&bytes).collect();
+        assert_eq!(words, vec!["quick".to_string()]);
+    }
+
+    #[test]
+    fn test_tokenizer_with_invalid_utf8_byte_between_tokens() {
+        let mut bytes = b"quick ".to_vec();
+        bytes.push(0xff);
+        bytes.extend_from_slice(b" brown");
+
+        let words: Vec<String> = WordTokenizer::new(&bytes).collect();
+        assert_eq!(words, vec!["quick".to_string(), "brown".to_string()]);
+    }
+
+    #[test]
+    fn test_tokenizer_keeps_alphanumerics_together() {
+        let words = tokenize("agent007 reports");
+        assert_eq!(words, vec!["agent007".to_string(), "reports".to_string()]);
+    }
+}